@@ -15,46 +15,170 @@
  */
 
 use std::collections::HashMap;
+#[cfg(feature = "tar")]
+use std::collections::BTreeMap;
 use std::env;
-use std::path::Path;
+#[cfg(feature = "tar")]
+use std::io;
+use std::path::{Path, PathBuf};
 
-use crate::binding::{Binding, CacheBinding, HashMapBinding};
+use crate::binding::{Binding, CacheBinding, ConfigTreeBinding, InvalidBindingError, MergedBinding};
+#[cfg(feature = "tar")]
+use crate::binding::HashMapBinding;
 
 pub const SERVICE_BINDING_ROOT: &str = "SERVICE_BINDING_ROOT";
 
+pub const CNB_BINDINGS: &str = "CNB_BINDINGS";
+
+/// The directory that Docker and Podman mount secrets into.
+pub const DOCKER_SECRETS_ROOT: &str = "/run/secrets";
+
+/// The environment variable that systemd sets to the directory of a unit's credentials.
+pub const CREDENTIALS_DIRECTORY: &str = "CREDENTIALS_DIRECTORY";
+
+/// The well-known directories that platforms project bindings into, in order of precedence.
+pub const DEFAULT_ROOTS: [&str; 2] = ["/bindings", "/platform/bindings"];
+
+/// The order in which roots take precedence when `merged`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Precedence {
+    /// Entries from earlier roots override entries from later roots.
+    First,
+
+    /// Entries from later roots override entries from earlier roots.
+    Last,
+}
+
 /// Wraps each `Binding` in a `CacheBinding`.
 ///
 /// * `bindings` - the bindings to wrap
+///
 /// returns the wrapped `Binding`s
-pub fn cached<'a>(bindings: Vec<impl Binding + 'a>) -> Vec<impl Binding + 'a> {
+pub fn cached<'a>(bindings: Vec<impl Binding + Send + Sync + 'a>) -> Vec<impl Binding + 'a> {
     return bindings.into_iter()
         .map(|v| CacheBinding::new(v))
         .collect();
 }
 
+/// Wraps each `Binding` in a `CacheBinding` behind a trait object, so that the result can be stored without naming
+/// the type of the `Binding`s.
+///
+/// * `bindings` - the bindings to wrap
+///
+/// returns the wrapped `Binding`s
+pub fn cached_boxed<'a>(bindings: Vec<impl Binding + Send + Sync + 'a>) -> Vec<Box<dyn Binding + Send + Sync + 'a>> {
+    return bindings.into_iter()
+        .map(|v| Box::new(CacheBinding::new(v)) as Box<dyn Binding + Send + Sync + 'a>)
+        .collect();
+}
+
 /// Creates a new collection of `Binding`s using the specified root.  If the directory does not exist, an empty
-/// collection is returned.
+/// collection is returned.  The `Binding`s are sorted by name, so that the order does not depend on the filesystem.
 ///
 /// * `root` - the root to populate the `Binding`s from
-/// returns the `Binding`s found in the root
+///
+/// returns the `Binding`s found in the root, sorted by name
 pub fn from(root: impl AsRef<Path>) -> Vec<impl Binding> {
-    let p = root.as_ref();
+    return config_trees(root.as_ref());
+}
 
+fn config_trees(p: &Path) -> Vec<ConfigTreeBinding> {
     if !p.exists() || !p.is_dir() {
         return Vec::new();
     }
 
-    return p.read_dir().map_or(Vec::new(), |b| {
+    return sorted(p.read_dir().map_or(Vec::new(), |b| {
         return b.filter_map(|c| {
             return c.map_or(None, |c| {
                 if !c.path().is_dir() {
                     return None;
                 }
 
-                return Some(HashMapBinding::new(c.file_name().to_str().unwrap(), HashMap::new()));
+                return Some(ConfigTreeBinding::new(c.path()));
             });
         }).collect();
-    });
+    }));
+}
+
+/// Returns a collection of `Binding`s sorted by name case-insensitively, so that results are deterministic regardless
+/// of the order in which they were read.  Names that differ only in case are ordered by their exact form.
+///
+/// * `bindings` - the `Binding`s to sort
+///
+/// returns the `Binding`s sorted by name
+pub fn sorted<B: Binding>(bindings: Vec<B>) -> Vec<B> {
+    let mut b: Vec<(String, B)> = bindings.into_iter()
+        .map(|b| (b.get_name(), b))
+        .collect();
+
+    b.sort_by(|(m, _), (n, _)| m.to_lowercase().cmp(&n.to_lowercase()).then_with(|| m.cmp(n)));
+    return b.into_iter().map(|(_, b)| b).collect();
+}
+
+/// Creates a new collection of `Binding`s using the specified root, behind trait objects so that they can be stored
+/// without naming their type or mixed with other implementations of `Binding`.  The trait objects are `Send + Sync`, so
+/// the result can be passed to `cached` or `cached_boxed`.  Otherwise identical to `from`.
+///
+/// * `root` - the root to populate the `Binding`s from
+///
+/// returns the `Binding`s found in the root, sorted by name
+pub fn from_boxed(root: impl AsRef<Path>) -> Vec<Box<dyn Binding + Send + Sync>> {
+    return config_trees(root.as_ref()).into_iter()
+        .map(|b| Box::new(b) as Box<dyn Binding + Send + Sync>)
+        .collect();
+}
+
+/// Creates an iterator over the `Binding`s in the specified root that reads the directory as it is consumed, so that
+/// callers can stop early without constructing every `Binding`.  If the directory does not exist, the iterator is
+/// empty.
+///
+/// * `root` - the root to populate the `Binding`s from
+///
+/// returns an iterator over the `Binding`s found in the root
+pub fn iter_from(root: impl AsRef<Path>) -> impl Iterator<Item=impl Binding> {
+    return root.as_ref().read_dir().into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .map(ConfigTreeBinding::new);
+}
+
+/// Creates a new collection of in-memory `Binding`s from a tar archive laid out as `<name>/<key>` entries, so that
+/// `Binding`s can be distributed as a single artifact.  Entries that are not regular files at that depth are ignored.
+///
+/// * `reader` - the tar archive to read
+///
+/// returns the `Binding`s found in the archive, sorted by name, or an error if the archive cannot be read
+#[cfg(feature = "tar")]
+pub fn from_tar(reader: impl io::Read) -> Result<Vec<HashMapBinding>, io::Error> {
+    let mut bindings: BTreeMap<String, HashMap<String, Vec<u8>>> = BTreeMap::new();
+
+    for e in tar::Archive::new(reader).entries()? {
+        let mut e = e?;
+
+        if !e.header().entry_type().is_file() {
+            continue;
+        }
+
+        let p = e.path()?.into_owned();
+        let c: Vec<&str> = p.iter().filter_map(|c| c.to_str()).filter(|c| *c != ".").collect();
+
+        let [n, k] = c[..] else {
+            continue;
+        };
+
+        let (n, k) = (n.to_string(), k.to_string());
+
+        let mut v = Vec::new();
+        io::Read::read_to_end(&mut e, &mut v)?;
+
+        bindings.entry(n).or_default().insert(k, v);
+    }
+
+    return Ok(bindings.into_iter()
+        .map(|(n, c)| HashMapBinding::new(n, c))
+        .collect());
 }
 
 /// Creates a new collection of `Binding`s using the `$SERVICE_BINDING_ROOT` environment variable to determine the file
@@ -69,18 +193,175 @@ pub fn from_service_binding_root() -> Vec<impl Binding> {
     };
 }
 
+/// Creates a `Binding` from the secrets that Docker Compose, Docker Swarm, and Podman mount into `/run/secrets`.  That
+/// directory is flat, with one file per secret rather than one directory per binding, so all of the secrets are
+/// grouped into a single `Binding` named `secrets` whose keys are the names of the secrets.
+///
+/// returns the `Binding` if `/run/secrets` exists, otherwise `None`
+pub fn from_docker_secrets() -> Option<impl Binding> {
+    return from_docker_secrets_in(Path::new(DOCKER_SECRETS_ROOT));
+}
+
+fn from_docker_secrets_in(root: &Path) -> Option<ConfigTreeBinding> {
+    if !root.is_dir() {
+        return None;
+    }
+
+    return Some(ConfigTreeBinding::new(root));
+}
+
+/// Creates a `Binding` from the credentials that systemd exposes in the directory named by `$CREDENTIALS_DIRECTORY`.
+/// Like `from_docker_secrets`, that directory is flat, with one file per credential, so all of the credentials are
+/// grouped into a single `Binding` whose keys are the names of the credentials.
+///
+/// returns the `Binding` if `$CREDENTIALS_DIRECTORY` is set and exists, otherwise `None`
+pub fn from_systemd_credentials() -> Option<impl Binding> {
+    return env::var_os(CREDENTIALS_DIRECTORY)
+        .and_then(|v| from_docker_secrets_in(Path::new(&v)));
+}
+
+/// Creates a new collection of `Binding`s by merging the `Binding`s found in several roots.  `Binding`s with the same
+/// name in different roots are merged into a single `Binding` whose entries are read from the root with the highest
+/// precedence that contains them.  Roots that do not exist are ignored.
+///
+/// * `roots` - the roots to populate the `Binding`s from
+/// * `precedence` - the order in which the roots take precedence
+///
+/// returns the merged `Binding`s found in the roots
+pub fn merged(roots: &[impl AsRef<Path>], precedence: Precedence) -> Vec<impl Binding> {
+    let mut r: Vec<&Path> = roots.iter().map(|r| r.as_ref()).collect();
+
+    if precedence == Precedence::Last {
+        r.reverse();
+    }
+
+    let mut names: Vec<String> = Vec::new();
+    let mut delegates: HashMap<String, Vec<Box<dyn Binding + Send + Sync>>> = HashMap::new();
+
+    for p in r {
+        let Ok(d) = p.read_dir() else {
+            continue;
+        };
+
+        for c in d.flatten() {
+            if !c.path().is_dir() {
+                continue;
+            }
+
+            let Some(n) = c.file_name().to_str().map(String::from) else {
+                continue;
+            };
+
+            if !delegates.contains_key(&n) {
+                names.push(n.to_string());
+            }
+
+            delegates.entry(n)
+                .or_default()
+                .push(Box::new(ConfigTreeBinding::new(c.path())));
+        }
+    }
+
+    return names.into_iter()
+        .map(|n| {
+            let d = delegates.remove(&n).unwrap();
+            return MergedBinding::new(n, d);
+        })
+        .collect();
+}
+
+/// Returns OpenMetrics text reporting a `service_binding_present` gauge for each `Binding`, labeled with its name and,
+/// if it exists, its type.  No entry values are included.
+///
+/// * `bindings` - the `Binding`s to report
+///
+/// returns the OpenMetrics text reporting the presence of the `Binding`s
+pub fn presence_metrics(bindings: &[impl Binding]) -> String {
+    let mut s = String::from("# TYPE service_binding_present gauge\n");
+
+    for b in bindings {
+        s.push_str(&format!("service_binding_present{{name=\"{}\"", escape_label(&b.get_name())));
+
+        if let Ok(t) = b.get_type() {
+            s.push_str(&format!(",type=\"{}\"", escape_label(&t)));
+        }
+
+        s.push_str("} 1\n");
+    }
+
+    s.push_str("# EOF\n");
+    return s;
+}
+
+fn escape_label(s: &str) -> String {
+    return s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+}
+
+/// Discovers the root of the `Binding`s by checking the `$SERVICE_BINDING_ROOT` environment variable, then the
+/// `$CNB_BINDINGS` environment variable, and then each of `DEFAULT_ROOTS`, returning the first that is an existing
+/// directory.
+///
+/// returns the root of the `Binding`s if one exists, otherwise `None`
+pub fn discover_root() -> Option<PathBuf> {
+    return discover_root_in(&DEFAULT_ROOTS);
+}
+
+fn discover_root_in(defaults: &[&str]) -> Option<PathBuf> {
+    return [SERVICE_BINDING_ROOT, CNB_BINDINGS].iter()
+        .filter_map(|v| env::var_os(v).map(PathBuf::from))
+        .chain(defaults.iter().map(PathBuf::from))
+        .find(|p| p.is_dir());
+}
+
+/// Creates a new collection of `Binding`s using the root found by `discover_root`.  If no root is found, an empty
+/// collection is returned.
+///
+/// returns the `Binding`s found in the discovered root
+pub fn from_discovered_root() -> Vec<impl Binding> {
+    return match discover_root() {
+        Some(p) => from(p),
+        None => Vec::new(),
+    };
+}
+
+/// Normalizes a label, such as a name, type, or provider, for comparison by trimming any whitespace and converting it
+/// to lowercase.
+///
+/// * `s` - the label to normalize
+///
+/// returns the normalized label
+pub fn normalize_label(s: &str) -> String {
+    return s.trim().to_lowercase();
+}
+
 /// Returns a `Binding` with a given name.  Comparison is case insensitive.
 ///
 /// * `bindings` - the `Binding`s to find in
 /// * `name` - the name of the `Binding` to find
+///
 /// returns the `Binding` with a given name if it exists.
 pub fn find(bindings: Vec<impl Binding>, name: &str) -> Option<impl Binding> {
     return bindings.into_iter()
-        .find(|b| b.get_name().eq_ignore_ascii_case(name));
+        .find(|b| normalize_label(&b.get_name()) == normalize_label(name));
+}
+
+/// Returns a `Binding` with a given name.  Unlike `find`, comparison is exact, for platforms where names that differ
+/// only by case identify different `Binding`s.
+///
+/// * `bindings` - the `Binding`s to find in
+/// * `name` - the name of the `Binding` to find
+///
+/// returns the `Binding` with a given name if it exists.
+pub fn find_case_sensitive(bindings: Vec<impl Binding>, name: &str) -> Option<impl Binding> {
+    return bindings.into_iter()
+        .find(|b| b.get_name() == name);
 }
 
 /// Returns zero or more `Binding`s with a given type and provider.  If type or provider are `None`, the result is not
-/// filtered on that argument.  Comparisons are case-insensitive.
+/// filtered on that argument.  Comparisons are case-insensitive.  A `Binding` without a type or provider does not match
+/// a filter on that argument.
 ///
 /// * `bindings` - the `Binding`s to filter
 /// * `binding_type` - the type of the `Binding` to find
@@ -91,15 +372,18 @@ pub fn filter_with_provider(bindings: Vec<impl Binding>, binding_type: Option<&s
     return bindings.into_iter()
         .filter(|b| {
             if let Some(t) = &binding_type {
-                if !b.get_type().unwrap().eq_ignore_ascii_case(t) {
-                    return false;
+                match b.get_type() {
+                    Err(_) => return false,
+                    Ok(u) => if normalize_label(&u) != normalize_label(t) {
+                        return false;
+                    },
                 }
             }
 
             if let Some(p) = &provider {
                 match b.get_provider() {
                     None => return false,
-                    Some(q) => if !q.eq_ignore_ascii_case(p) {
+                    Some(q) => if normalize_label(&q) != normalize_label(p) {
                         return false;
                     },
                 }
@@ -110,10 +394,47 @@ pub fn filter_with_provider(bindings: Vec<impl Binding>, binding_type: Option<&s
         .collect();
 }
 
+/// Returns zero or more `Binding`s whose type is any of several types, such as aliases for the same service.
+/// Comparisons are case-insensitive.  A `Binding` without a type does not match.
+///
+/// * `bindings` - the `Binding`s to filter
+/// * `types` - the types of the `Binding`s to find
+///
+/// returns the collection of `Binding`s with any of the types
+pub fn filter_by_types(bindings: Vec<impl Binding>, types: &[&str]) -> Vec<impl Binding> {
+    let types: Vec<String> = types.iter().map(|t| normalize_label(t)).collect();
+
+    return bindings.into_iter()
+        .filter(|b| b.get_type_normalized().is_ok_and(|t| types.contains(&t)))
+        .collect();
+}
+
+/// Returns zero or more `Binding`s with a given type and provider, failing if any `Binding` is malformed.  Unlike
+/// `filter_with_provider`, a `Binding` without a type is an error rather than non-matching, so that misconfiguration can
+/// be reported at startup.
+///
+/// * `bindings` - the `Binding`s to filter
+/// * `binding_type` - the type of `Binding` to find
+/// * `provider` - the provider of `Binding` to find
+///
+/// returns the collection of `Binding`s with a given type and provider, or an error naming each malformed `Binding`
+pub fn try_filter_with_provider(bindings: Vec<impl Binding>, binding_type: Option<&str>, provider: Option<&str>) -> Result<Vec<impl Binding>, Vec<InvalidBindingError>> {
+    let errors: Vec<InvalidBindingError> = bindings.iter()
+        .filter_map(|b| b.get_type().err().map(|e| InvalidBindingError::new(format!("binding {}: {}", b.get_name(), e))))
+        .collect();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    return Ok(filter_with_provider(bindings, binding_type, provider));
+}
+
 /// Returns zero or more `Binding`s with a given type.  Equivalent to `filter_with_provider` with a `None` provider.
 ///
 /// * `bindings` - the `Binding`s to filter
 /// * `binding_type` - the type of the `Binding` to find
+///
 /// returns zero or more `Bindings` with a given type
 pub fn filter(bindings: Vec<impl Binding>, binding_type: &str) -> Vec<impl Binding> {
     return filter_with_provider(bindings, Some(binding_type), None);
@@ -122,13 +443,15 @@ pub fn filter(bindings: Vec<impl Binding>, binding_type: &str) -> Vec<impl Bindi
 #[cfg(test)]
 mod tests {
     use std::env;
+    use std::fs;
     use std::any::Any;
     use std::collections::HashMap;
+    use std::path::PathBuf;
     use std::sync::Mutex;
 
     use lazy_static::lazy_static;
 
-    use crate::binding::{Binding, CacheBinding, HashMapBinding};
+    use crate::binding::{Binding, CacheBinding, HashMapBinding, InvalidBindingError, RecordingBinding};
     use crate::bindings;
 
     lazy_static! {
@@ -163,7 +486,161 @@ mod tests {
 
     #[test]
     fn from_valid() {
-        assert_eq!(3, bindings::from("testdata").len());
+        assert_eq!(11, bindings::from("testdata").len());
+    }
+
+    #[test]
+    fn from_reads_contents() {
+        let b = bindings::filter(bindings::from("testdata"), "postgresql");
+        assert_eq!(1, b.len());
+        assert_eq!(Some(String::from("postgresql://test-host:5432/test-database")), b[0].get("url"));
+    }
+
+    #[test]
+    fn from_sorted() {
+        let n: Vec<String> = bindings::from("testdata").iter().map(|b| b.get_name()).collect();
+        let mut m = n.clone();
+        m.sort_by_key(|n| n.to_lowercase());
+
+        assert_eq!(m, n);
+    }
+
+    #[test]
+    fn sorted() {
+        let b = vec![
+            HashMapBinding::new("test-name-3", HashMap::new()),
+            HashMapBinding::new("Test-Name-2", HashMap::new()),
+            HashMapBinding::new("test-name-1", HashMap::new()),
+        ];
+
+        let n: Vec<String> = bindings::sorted(b).iter().map(|b| b.get_name()).collect();
+        assert_eq!(vec!["test-name-1", "Test-Name-2", "test-name-3"], n);
+    }
+
+    #[test]
+    fn from_boxed() {
+        let mut b = bindings::from_boxed("testdata");
+        let n = b.len();
+
+        b.push(Box::new(HashMapBinding::new("test-name", map! {
+            "type" => "test-type-1",
+        })));
+
+        assert_eq!(n + 1, b.len());
+        assert_eq!(Some(String::from("test-type-1")), bindings::find(b, "test-name").and_then(|b| b.get_type().ok()));
+    }
+
+    #[test]
+    fn cached_boxed() {
+        let b = bindings::cached_boxed(vec![HashMapBinding::new("test-name", map! {
+            "type" => "test-type-1",
+        })]);
+
+        assert_eq!(1, bindings::filter(b, "test-type-1").len());
+    }
+
+    #[test]
+    fn cached_boxed_from_boxed() {
+        let b = bindings::cached_boxed(bindings::from_boxed("testdata"));
+        let c = bindings::find(b, "test-k8s").unwrap();
+
+        assert_eq!(Some(String::from("test-secret-value")), c.get("test-secret-key"));
+    }
+
+    #[test]
+    fn cached_wrapper() {
+        let b = bindings::cached(vec![RecordingBinding::new(HashMapBinding::new("test-name", map! {
+            "type" => "test-type-1",
+        }))]);
+
+        assert_eq!(Ok(String::from("test-type-1")), b[0].get_type());
+    }
+
+    #[test]
+    fn from_docker_secrets_in() {
+        let d = tempfile::tempdir().unwrap();
+        let r = d.path().join("secrets");
+        fs::create_dir(&r).unwrap();
+        fs::write(r.join("db-password"), "test-password\n").unwrap();
+        fs::write(r.join("api-key"), "test-api-key").unwrap();
+
+        let b = bindings::from_docker_secrets_in(&r).unwrap();
+        assert_eq!("secrets", b.get_name());
+        assert_eq!(vec!["api-key", "db-password"], b.get_keys_sorted());
+        assert_eq!(Some(String::from("test-password")), b.get("db-password"));
+    }
+
+    #[test]
+    fn from_docker_secrets_in_missing() {
+        let d = tempfile::tempdir().unwrap();
+        assert!(bindings::from_docker_secrets_in(&d.path().join("secrets")).is_none());
+    }
+
+    #[test]
+    fn iter_from_first() {
+        let mut i = bindings::iter_from("testdata");
+        let b = i.next().unwrap();
+
+        assert!(PathBuf::from("testdata").join(b.get_name()).is_dir());
+    }
+
+    #[test]
+    fn iter_from_lazy() {
+        let d = tempfile::tempdir().unwrap();
+        for n in ["test-name-1", "test-name-2", "test-name-3"] {
+            fs::create_dir(d.path().join(n)).unwrap();
+        }
+
+        let mut i = bindings::iter_from(d.path());
+        let b = i.next().unwrap();
+
+        for n in ["test-name-1", "test-name-2", "test-name-3"].into_iter().filter(|n| *n != b.get_name()) {
+            fs::remove_dir(d.path().join(n)).unwrap();
+        }
+
+        assert_eq!(0, i.count());
+    }
+
+    #[test]
+    fn iter_from_valid() {
+        assert_eq!(11, bindings::iter_from("testdata").count());
+        assert!(bindings::iter_from("testdata").any(|b| b.get("test-secret-key").is_some()));
+    }
+
+    #[test]
+    fn iter_from_invalid() {
+        assert_eq!(0, bindings::iter_from("missing").count());
+        assert_eq!(0, bindings::iter_from("testdata/additional-file").count());
+    }
+
+    #[cfg(feature = "tar")]
+    #[test]
+    fn from_tar() {
+        let mut a = tar::Builder::new(Vec::new());
+
+        for (p, v) in [
+            ("test-name-2/type", "test-type-2"),
+            ("test-name-1/type", "test-type-1"),
+            ("test-name-1/test-secret-key", "test-secret-value\n"),
+            ("additional-file", "test-additional-value"),
+        ] {
+            let mut h = tar::Header::new_gnu();
+            h.set_size(v.len() as u64);
+            h.set_mode(0o644);
+            h.set_cksum();
+            a.append_data(&mut h, p, v.as_bytes()).unwrap();
+        }
+
+        let b = bindings::from_tar(a.into_inner().unwrap().as_slice()).unwrap();
+        assert_eq!(vec!["test-name-1", "test-name-2"], b.iter().map(|b| b.get_name()).collect::<Vec<_>>());
+        assert_eq!(Some(String::from("test-secret-value")), b[0].get("test-secret-key"));
+        assert_eq!(Ok(String::from("test-type-2")), b[1].get_type());
+    }
+
+    #[cfg(feature = "tar")]
+    #[test]
+    fn from_tar_invalid() {
+        assert!(bindings::from_tar("not a tar archive".as_bytes()).is_err());
     }
 
     #[test]
@@ -179,7 +656,109 @@ mod tests {
         let old = env::var_os("SERVICE_BINDING_ROOT");
         env::set_var("SERVICE_BINDING_ROOT", "testdata");
 
-        assert_eq!(3, bindings::from_service_binding_root().len());
+        assert_eq!(11, bindings::from_service_binding_root().len());
+
+        match old {
+            None => env::remove_var("SERVICE_BINDING_ROOT"),
+            Some(v) => env::set_var("SERVICE_BINDING_ROOT", v),
+        }
+        drop(g)
+    }
+
+    #[test]
+    fn from_systemd_credentials_unset() {
+        let g = MUTEX.lock().unwrap();
+        let old = env::var_os("CREDENTIALS_DIRECTORY");
+        env::remove_var("CREDENTIALS_DIRECTORY");
+
+        assert!(bindings::from_systemd_credentials().is_none());
+
+        if let Some(v) = old {
+            env::set_var("CREDENTIALS_DIRECTORY", v);
+        }
+        drop(g)
+    }
+
+    #[test]
+    fn from_systemd_credentials_set() {
+        let d = tempfile::tempdir().unwrap();
+        let r = d.path().join("test-unit");
+        fs::create_dir(&r).unwrap();
+        fs::write(r.join("db-password"), "test-password\n").unwrap();
+
+        let g = MUTEX.lock().unwrap();
+        let old = env::var_os("CREDENTIALS_DIRECTORY");
+        env::set_var("CREDENTIALS_DIRECTORY", &r);
+
+        let b = bindings::from_systemd_credentials().unwrap();
+        assert_eq!("test-unit", b.get_name());
+        assert_eq!(vec!["db-password"], b.get_keys());
+        assert_eq!(Some(String::from("test-password")), b.get("db-password"));
+
+        match old {
+            None => env::remove_var("CREDENTIALS_DIRECTORY"),
+            Some(v) => env::set_var("CREDENTIALS_DIRECTORY", v),
+        }
+        drop(g)
+    }
+
+    #[test]
+    fn merged_first() {
+        let b = bindings::merged(&["testdata/test-merged/overlay", "testdata/test-merged/base", "missing"], bindings::Precedence::First);
+        assert_eq!(2, b.len());
+
+        let c = bindings::find(b, "test-name-1").unwrap();
+        assert_eq!(Some(String::from("overlay-host")), c.get("host"));
+        assert_eq!(Some(String::from("base-username")), c.get("username"));
+        assert_eq!(Ok(String::from("test-type-1")), c.get_type());
+    }
+
+    #[test]
+    fn merged_last() {
+        let b = bindings::merged(&["testdata/test-merged/overlay", "testdata/test-merged/base"], bindings::Precedence::Last);
+
+        let c = bindings::find(b, "test-name-1").unwrap();
+        assert_eq!(Some(String::from("base-host")), c.get("host"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn merged_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let d = tempfile::tempdir().unwrap();
+        fs::create_dir(d.path().join(OsStr::from_bytes(&[0xfe, 0xed]))).unwrap();
+        fs::create_dir(d.path().join("test-name-1")).unwrap();
+
+        let b = bindings::merged(&[d.path()], bindings::Precedence::First);
+        assert_eq!(1, b.len());
+        assert_eq!("test-name-1", b[0].get_name());
+    }
+
+    #[test]
+    fn presence_metrics() {
+        let b = vec![
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "test-type-1",
+                "password" => "test-password",
+            }),
+            HashMapBinding::new("test-name-2", HashMap::new()),
+        ];
+
+        assert_eq!("# TYPE service_binding_present gauge\n\
+                    service_binding_present{name=\"test-name-1\",type=\"test-type-1\"} 1\n\
+                    service_binding_present{name=\"test-name-2\"} 1\n\
+                    # EOF\n", bindings::presence_metrics(&b));
+    }
+
+    #[test]
+    fn discover_root_service_binding_root() {
+        let g = MUTEX.lock().unwrap();
+        let old = env::var_os("SERVICE_BINDING_ROOT");
+        env::set_var("SERVICE_BINDING_ROOT", "testdata");
+
+        assert_eq!(Some(PathBuf::from("testdata")), bindings::discover_root_in(&["testdata/test-k8s"]));
 
         match old {
             None => env::remove_var("SERVICE_BINDING_ROOT"),
@@ -188,6 +767,29 @@ mod tests {
         drop(g)
     }
 
+    #[test]
+    fn discover_root_cnb_bindings() {
+        let g = MUTEX.lock().unwrap();
+        let old = env::var_os("CNB_BINDINGS");
+        env::set_var("CNB_BINDINGS", "testdata/test-merged/base");
+
+        assert_eq!(Some(PathBuf::from("testdata/test-merged/base")), bindings::discover_root_in(&["testdata/test-k8s"]));
+
+        match old {
+            None => env::remove_var("CNB_BINDINGS"),
+            Some(v) => env::set_var("CNB_BINDINGS", v),
+        }
+        drop(g)
+    }
+
+    #[test]
+    fn discover_root_default() {
+        let g = MUTEX.lock().unwrap();
+        assert_eq!(Some(PathBuf::from("testdata/test-k8s")), bindings::discover_root_in(&["missing", "testdata/additional-file", "testdata/test-k8s"]));
+        assert_eq!(None, bindings::discover_root_in(&["missing"]));
+        drop(g)
+    }
+
     #[test]
     fn find_missing() {
         let b = vec![
@@ -197,6 +799,15 @@ mod tests {
         assert!(bindings::find(b, "test-name-2").is_none())
     }
 
+    #[test]
+    fn find_normalized() {
+        let b = vec![
+            HashMapBinding::new("Test-Name-1", HashMap::new()),
+        ];
+
+        assert!(bindings::find(b, " test-name-1 ").is_some())
+    }
+
     #[test]
     fn find_valid() {
         let b = vec![
@@ -207,6 +818,19 @@ mod tests {
         assert_eq!(Some(String::from("test-name-1")), bindings::find(b, "test-name-1").map(|q| q.get_name()))
     }
 
+    #[test]
+    fn find_case_sensitive() {
+        let d = tempfile::tempdir().unwrap();
+        for n in ["URL", "url"] {
+            fs::create_dir(d.path().join(n)).unwrap();
+            fs::write(d.path().join(n).join("type"), n).unwrap();
+        }
+
+        assert_eq!(Some(String::from("URL")), bindings::find_case_sensitive(bindings::from(d.path()), "URL").map(|b| b.get("type").unwrap()));
+        assert_eq!(Some(String::from("url")), bindings::find_case_sensitive(bindings::from(d.path()), "url").map(|b| b.get("type").unwrap()));
+        assert!(bindings::find_case_sensitive(bindings::from(d.path()), "Url").is_none())
+    }
+
     #[test]
     fn filter_none() {
         let b = vec![
@@ -300,6 +924,105 @@ mod tests {
         assert_eq!(1, bindings::filter_with_provider(b, Some("test-type-1"), Some("test-provider-1")).len());
     }
 
+    #[test]
+    fn filter_type_missing() {
+        let b = vec![
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "test-type-1",
+                "provider" => "test-provider-1",
+            }),
+            HashMapBinding::new("test-name-2", map! {
+                "provider" => "test-provider-1",
+            }),
+            HashMapBinding::new("test-name-3", map! {
+                "type" => "test-type-2",
+            }),
+        ];
+
+        let b = bindings::filter_with_provider(b, Some("test-type-1"), None);
+        assert_eq!(1, b.len());
+        assert_eq!("test-name-1", b[0].get_name());
+    }
+
+    #[test]
+    fn filter_by_types() {
+        let b = vec![
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "postgresql",
+            }),
+            HashMapBinding::new("test-name-2", map! {
+                "type" => "Postgres",
+            }),
+            HashMapBinding::new("test-name-3", map! {
+                "type" => "mysql",
+            }),
+            HashMapBinding::new("test-name-4", map! {
+                "provider" => "test-provider-1",
+            }),
+        ];
+
+        let n: Vec<String> = bindings::filter_by_types(b, &["postgresql", "postgres"]).iter().map(|b| b.get_name()).collect();
+        assert_eq!(vec!["test-name-1", "test-name-2"], n);
+    }
+
+    #[test]
+    fn try_filter_with_provider() {
+        let b = vec![
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "test-type-1",
+                "provider" => "test-provider-1",
+            }),
+            HashMapBinding::new("test-name-2", map! {
+                "type" => "test-type-2",
+            }),
+        ];
+
+        assert_eq!(1, bindings::try_filter_with_provider(b, Some("test-type-1"), None).ok().unwrap().len());
+    }
+
+    #[test]
+    fn try_filter_with_provider_missing_type() {
+        let b = vec![
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "test-type-1",
+            }),
+            HashMapBinding::new("test-name-2", map! {
+                "provider" => "test-provider-1",
+            }),
+            HashMapBinding::new("test-name-3", map! {}),
+        ];
+
+        match bindings::try_filter_with_provider(b, Some("test-type-1"), None) {
+            Ok(_) => panic!("expected errors"),
+            Err(e) => assert_eq!(vec![
+                InvalidBindingError::new("binding test-name-2: binding does not contain a type"),
+                InvalidBindingError::new("binding test-name-3: binding does not contain a type"),
+            ], e),
+        }
+    }
+
+    #[test]
+    fn filter_normalized() {
+        let b = vec![
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "PostgreSQL",
+                "provider" => " Bitnami ",
+            }),
+            HashMapBinding::new("test-name-2", map! {
+                "type" => "postgresql",
+                "provider" => "crunchy",
+            }),
+        ];
+
+        assert_eq!(1, bindings::filter_with_provider(b, Some("postgresql"), Some("bitnami")).len());
+    }
+
+    #[test]
+    fn normalize_label() {
+        assert_eq!("bitnami", bindings::normalize_label(" Bitnami "));
+        assert_eq!("straße", bindings::normalize_label("STRAßE"));
+    }
+
     #[test]
     fn filter_overload() {
         let b = vec![
@@ -0,0 +1,158 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+use serde::de::{DeserializeOwned, Error as _, IntoDeserializer, Visitor};
+use serde::{forward_to_deserialize_any, Deserializer};
+
+/// Deserializes a type from the entries of a `Binding`.  Each key is a field name and each value is parsed on demand
+/// as the type of its field, so that an entry containing `5432` can populate either a `u16` or a `String`.
+///
+/// * `entries` - the trimmed UTF-8 entries of the `Binding`
+///
+/// returns the deserialized type, or an error if an entry cannot be parsed as the type of its field
+pub(crate) fn from_entries<T: DeserializeOwned>(entries: HashMap<String, String>) -> Result<T, Error> {
+    return T::deserialize(MapDeserializer::new(entries.into_iter().map(|(k, v)| (k, Entry(v)))));
+}
+
+/// A `Deserializer` for the value of a single `Binding` entry.  Empty values deserialize as `None` and each non-blank
+/// line is an item when the value is deserialized as a sequence.
+struct Entry(String);
+
+impl<'de> IntoDeserializer<'de, Error> for Entry {
+    type Deserializer = Entry;
+
+    fn into_deserializer(self) -> Entry {
+        return self;
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                return visitor.$visit(self.0.parse().map_err(|e| Error::custom(format!("invalid value {}: {}", self.0, e)))?);
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Entry {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        return visitor.visit_string(self.0);
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.0.is_empty() {
+            return visitor.visit_none();
+        }
+
+        return visitor.visit_some(self);
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        return visitor.visit_newtype_struct(self);
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let items: Vec<Entry> = self.0.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(|l| Entry(l.to_string()))
+            .collect();
+
+        return visitor.visit_seq(SeqDeserializer::new(items.into_iter()));
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+        return visitor.visit_enum(self.0.into_deserializer());
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use crate::de;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Mode {
+        Require,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+        enabled: bool,
+        hosts: Vec<String>,
+        mode: Mode,
+        password: Option<String>,
+        timeout: Option<f64>,
+    }
+
+    fn entries(e: &[(&str, &str)]) -> HashMap<String, String> {
+        return e.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    }
+
+    #[test]
+    fn from_entries() {
+        let c: Config = de::from_entries(entries(&[
+            ("enabled", "true"),
+            ("hosts", "test-host-1\n\ntest-host-2"),
+            ("mode", "require"),
+            ("password", ""),
+            ("unknown", "test-value"),
+        ])).unwrap();
+
+        assert_eq!(Config {
+            enabled: true,
+            hosts: vec![String::from("test-host-1"), String::from("test-host-2")],
+            mode: Mode::Require,
+            password: None,
+            timeout: None,
+        }, c)
+    }
+
+    #[test]
+    fn from_entries_invalid() {
+        let e = de::from_entries::<Config>(entries(&[("enabled", "yes")])).unwrap_err();
+        assert!(e.to_string().contains("invalid value yes"))
+    }
+}
@@ -0,0 +1,208 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::env;
+use std::io;
+use std::io::Write;
+use std::str;
+
+use crate::binding;
+use crate::binding::Binding;
+
+/// Writes the entries of a `Binding` as a Java `.properties` file, one `key=value` line per entry in key order.
+/// Keys and values are escaped following the `java.util.Properties` rules, with non-ASCII characters written as
+/// `\uXXXX` escapes.  Values are trimmed and entries whose values are not valid UTF-8 are skipped.
+///
+/// * `binding` - the `Binding` to write
+/// * `writer` - the destination to write to
+///
+/// returns `Ok` if the entries were written, otherwise the error returned by `writer`
+pub fn to_properties(binding: &impl Binding, mut writer: impl Write) -> io::Result<()> {
+    for (k, v) in binding.to_sorted_map() {
+        let Ok(v) = str::from_utf8(&v) else {
+            continue;
+        };
+
+        writeln!(writer, "{}={}", escape_property(&k, true), escape_property(v.trim(), false))?;
+    }
+
+    return Ok(());
+}
+
+/// Sets a `PREFIX_KEY` process environment variable for each entry of a `Binding`, so that existing loaders that read
+/// configuration from the environment pick the entries up.  Keys are mapped to names as in `EnvOverrideBinding`.  Values
+/// are trimmed and entries whose values are not valid UTF-8 are skipped.
+///
+/// This mutates the environment of the whole process, which is visible to, and not synchronized with, every other
+/// thread, so it should only be called during startup, before any other threads are started.
+///
+/// * `binding` - the `Binding` to read the entries from
+/// * `prefix` - the prefix of the environment variables to set
+pub fn apply_to_env(binding: &impl Binding, prefix: &str) {
+    for (k, v) in binding.to_sorted_map() {
+        let Ok(v) = str::from_utf8(&v) else {
+            continue;
+        };
+
+        env::set_var(binding::env_name(prefix, &k), v.trim());
+    }
+}
+
+/// Returns a JSON description of a set of `Binding`s suitable for a debug or introspection endpoint.  Each `Binding`
+/// is described by an object containing its `name`, `type`, `provider`, and sorted `keys`.  Values are never included.
+///
+/// * `bindings` - the `Binding`s to describe
+///
+/// returns an array of objects describing the `Binding`s
+#[cfg(feature = "serde")]
+pub fn to_debug_json(bindings: &[impl Binding]) -> serde_json::Value {
+    return bindings.iter()
+        .map(|b| {
+            let mut keys = b.get_keys();
+            keys.sort();
+
+            return serde_json::json!({
+                "name": b.get_name(),
+                "type": b.get_type().ok(),
+                "provider": b.get_provider(),
+                "keys": keys,
+            });
+        })
+        .collect();
+}
+
+fn escape_property(s: &str, key: bool) -> String {
+    let mut e = String::new();
+
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            ' ' if key || i == 0 => e.push_str("\\ "),
+            '\\' | '=' | ':' | '#' | '!' => {
+                e.push('\\');
+                e.push(c);
+            }
+            '\t' => e.push_str("\\t"),
+            '\n' => e.push_str("\\n"),
+            '\r' => e.push_str("\\r"),
+            '\x0c' => e.push_str("\\f"),
+            ' '..='~' => e.push(c),
+            _ => {
+                let mut b = [0; 2];
+                for u in c.encode_utf16(&mut b) {
+                    e.push_str(&format!("\\u{:04X}", u));
+                }
+            }
+        }
+    }
+
+    return e;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::env;
+    use std::sync::Mutex;
+
+    use lazy_static::lazy_static;
+
+    use crate::binding::HashMapBinding;
+    use crate::export;
+
+    lazy_static! {
+        static ref MUTEX: Mutex<()> = Mutex::default();
+    }
+
+    #[test]
+    fn to_properties() {
+        let mut c: HashMap<String, Vec<u8>> = map! {
+            "url" => "jdbc:postgresql://test-host/test-database?user=test\\name\n",
+            "greeting" => "héllo 😀",
+            "multi-line" => "line-1\nline-2",
+        };
+        c.insert(String::from("keystore"), vec![0xfe, 0xed, 0xfe, 0xed]);
+
+        let mut w = Vec::new();
+        export::to_properties(&HashMapBinding::new("test-name", c), &mut w).unwrap();
+
+        assert_eq!("greeting=h\\u00E9llo \\uD83D\\uDE00\n\
+                    multi-line=line-1\\nline-2\n\
+                    url=jdbc\\:postgresql\\://test-host/test-database?user\\=test\\\\name\n",
+                   String::from_utf8(w).unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn to_debug_json() {
+        let b = [
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "test-type-1",
+                "provider" => "test-provider-1",
+                "password" => "test-secret-value",
+            }),
+            HashMapBinding::new("test-name-2", map! {
+                "username" => "test-username",
+            }),
+        ];
+
+        let j = export::to_debug_json(&b);
+
+        assert_eq!(serde_json::json!([
+            {
+                "name": "test-name-1",
+                "type": "test-type-1",
+                "provider": "test-provider-1",
+                "keys": ["password", "provider", "type"],
+            },
+            {
+                "name": "test-name-2",
+                "type": null,
+                "provider": null,
+                "keys": ["username"],
+            },
+        ]), j);
+        assert!(!j.to_string().contains("test-secret-value"));
+        assert!(!j.to_string().contains("test-username"));
+    }
+
+    #[test]
+    fn apply_to_env() {
+        let mut c: HashMap<String, Vec<u8>> = map! {
+            "host" => "test-host\n",
+            "ssl-mode" => "require",
+        };
+        c.insert(String::from("keystore"), vec![0xfe, 0xed, 0xfe, 0xed]);
+
+        let g = MUTEX.lock().unwrap();
+        let old: Vec<_> = ["TEST_EXPORT_HOST", "TEST_EXPORT_SSL_MODE", "TEST_EXPORT_KEYSTORE"].iter()
+            .map(|k| (k, env::var_os(k)))
+            .collect();
+
+        export::apply_to_env(&HashMapBinding::new("test-name", c), "TEST_EXPORT");
+
+        assert_eq!(Ok(String::from("test-host")), env::var("TEST_EXPORT_HOST"));
+        assert_eq!(Ok(String::from("require")), env::var("TEST_EXPORT_SSL_MODE"));
+        assert!(env::var_os("TEST_EXPORT_KEYSTORE").is_none());
+
+        for (k, v) in old {
+            match v {
+                None => env::remove_var(k),
+                Some(v) => env::set_var(k, v),
+            }
+        }
+        drop(g)
+    }
+}
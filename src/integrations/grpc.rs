@@ -0,0 +1,153 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "tonic")]
+use crate::binding::{Binding, InvalidBindingError};
+
+/// The key for the endpoint URI of a gRPC `Binding`.
+pub const ENDPOINT: &str = "endpoint";
+
+/// The key for the PEM-encoded CA certificate of a gRPC `Binding`.
+pub const CA_CERTIFICATE: &str = "ca.crt";
+
+/// The key for the TLS authority of a gRPC `Binding`, when it differs from the host of the endpoint.
+pub const AUTHORITY: &str = "authority";
+
+/// The configuration of a gRPC channel.
+#[cfg(feature = "tonic")]
+#[derive(Clone, Debug)]
+pub struct ChannelConfig {
+    /// The URI of the endpoint to connect to.
+    pub endpoint: tonic::transport::Uri,
+
+    /// The TLS configuration of the channel, if the endpoint is `https` or the `Binding` contains a CA certificate.
+    pub tls: Option<tonic::transport::ClientTlsConfig>,
+}
+
+#[cfg(feature = "tonic")]
+impl ChannelConfig {
+    /// Creates a `tonic::transport::Endpoint` from this configuration.
+    ///
+    /// returns the `Endpoint` if the TLS configuration is valid, otherwise a `tonic::transport::Error`
+    pub fn to_endpoint(&self) -> Result<tonic::transport::Endpoint, tonic::transport::Error> {
+        let e = tonic::transport::Endpoint::from(self.endpoint.clone());
+
+        return match &self.tls {
+            None => Ok(e),
+            Some(t) => e.tls_config(t.clone()),
+        };
+    }
+}
+
+/// Creates a gRPC `ChannelConfig` from a gRPC `Binding`.  If the `Binding` contains a CA certificate, TLS is configured
+/// to trust it.  Otherwise, `https` endpoints are configured with the trust roots enabled by `tonic` features.  In
+/// either case, the server is verified as `authority` if present.
+///
+/// * `binding` - the `Binding` to read the configuration from
+///
+/// returns the `ChannelConfig` if the `Binding` contains a valid absolute `endpoint`, otherwise an
+/// `InvalidBindingError`
+#[cfg(feature = "tonic")]
+pub fn channel_config(binding: &impl Binding) -> Result<ChannelConfig, InvalidBindingError> {
+    use tonic::transport::{Certificate, ClientTlsConfig, Uri};
+
+    let e = binding.get(ENDPOINT)
+        .ok_or_else(|| InvalidBindingError::new("binding does not contain an endpoint"))?;
+
+    let endpoint = e.parse::<Uri>()
+        .map_err(|err| InvalidBindingError::new(format!("invalid endpoint {}: {}", e, err)))?;
+
+    if endpoint.scheme().is_none() || endpoint.authority().is_none() {
+        return Err(InvalidBindingError::new(format!("invalid endpoint {}: must be an absolute URI", e)));
+    }
+
+    let tls = match binding.get_as_bytes(CA_CERTIFICATE) {
+        Some(c) => Some(ClientTlsConfig::new().ca_certificate(Certificate::from_pem(c))),
+        None if endpoint.scheme_str() == Some("https") => Some(ClientTlsConfig::new().with_enabled_roots()),
+        None => None,
+    };
+
+    let tls = tls.map(|t| {
+        return match binding.get(AUTHORITY) {
+            Some(a) => t.domain_name(a),
+            None => t,
+        };
+    });
+
+    return Ok(ChannelConfig { endpoint, tls });
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "tonic")]
+    use crate::binding::{ConfigTreeBinding, HashMapBinding, InvalidBindingError};
+    #[cfg(feature = "tonic")]
+    use crate::integrations::grpc;
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn channel_config_tls() {
+        let c = grpc::channel_config(&ConfigTreeBinding::new("testdata/test-grpc")).unwrap();
+        assert_eq!("https://test-host:8443/", c.endpoint.to_string());
+        assert!(c.tls.is_some());
+        assert_eq!(&c.endpoint, c.to_endpoint().unwrap().uri());
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn channel_config_https_default_tls() {
+        let b = HashMapBinding::new("test-name", map! {
+            "endpoint" => "https://test-host:8443",
+        });
+
+        let c = grpc::channel_config(&b).unwrap();
+        assert!(c.tls.is_some());
+        assert_eq!(&c.endpoint, c.to_endpoint().unwrap().uri());
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn channel_config_plaintext() {
+        let b = HashMapBinding::new("test-name", map! {
+            "endpoint" => "http://test-host:9090",
+        });
+
+        let c = grpc::channel_config(&b).unwrap();
+        assert_eq!("http://test-host:9090/", c.endpoint.to_string());
+        assert!(c.tls.is_none());
+        assert_eq!(&c.endpoint, c.to_endpoint().unwrap().uri());
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn channel_config_missing_endpoint() {
+        let b = HashMapBinding::new("test-name", map! {
+            "authority" => "test-authority",
+        });
+
+        assert_eq!(Some(InvalidBindingError::new("binding does not contain an endpoint")), grpc::channel_config(&b).err())
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn channel_config_relative_endpoint() {
+        let b = HashMapBinding::new("test-name", map! {
+            "endpoint" => "/test-path",
+        });
+
+        assert_eq!(Some(InvalidBindingError::new("invalid endpoint /test-path: must be an absolute URI")), grpc::channel_config(&b).err())
+    }
+}
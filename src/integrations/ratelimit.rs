@@ -0,0 +1,99 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::binding::{Binding, InvalidBindingError};
+
+/// The key for the sustained request rate of a rate-limit `Binding`.
+pub const REQUESTS_PER_SECOND: &str = "requests-per-second";
+
+/// The key for the burst size of a rate-limit `Binding`.
+pub const BURST: &str = "burst";
+
+/// The configuration of a rate limiter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The sustained number of requests allowed per second.
+    pub rps: u32,
+
+    /// The number of requests allowed in a single burst.
+    pub burst: u32,
+}
+
+/// Creates a rate limiter `Config` from a rate-limit `Binding`.  The burst size defaults to the request rate.
+///
+/// * `binding` - the `Binding` to read the configuration from
+///
+/// returns the `Config` if the `Binding` contains a valid `requests-per-second`, otherwise an `InvalidBindingError`
+pub fn config(binding: &impl Binding) -> Result<Config, InvalidBindingError> {
+    let parse = |k: &str, v: String| {
+        return v.parse::<u32>()
+            .map_err(|e| InvalidBindingError::new(format!("invalid {} {}: {}", k, v, e)));
+    };
+
+    let rps = binding.get(REQUESTS_PER_SECOND)
+        .ok_or_else(|| InvalidBindingError::new("binding does not contain requests-per-second"))
+        .and_then(|v| parse(REQUESTS_PER_SECOND, v))?;
+
+    let burst = binding.get(BURST)
+        .map(|v| parse(BURST, v))
+        .transpose()?
+        .unwrap_or(rps);
+
+    return Ok(Config { rps, burst });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::binding::{HashMapBinding, InvalidBindingError};
+    use crate::integrations::ratelimit;
+    use crate::integrations::ratelimit::Config;
+
+    #[test]
+    fn config_explicit_burst() {
+        let b = HashMapBinding::new("test-name", map! {
+            "requests-per-second" => "100",
+            "burst" => "250",
+        });
+
+        assert_eq!(Ok(Config { rps: 100, burst: 250 }), ratelimit::config(&b))
+    }
+
+    #[test]
+    fn config_defaulted_burst() {
+        let b = HashMapBinding::new("test-name", map! {
+            "requests-per-second" => "100\n",
+        });
+
+        assert_eq!(Ok(Config { rps: 100, burst: 100 }), ratelimit::config(&b))
+    }
+
+    #[test]
+    fn config_missing_rps() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert_eq!(Err(InvalidBindingError::new("binding does not contain requests-per-second")), ratelimit::config(&b))
+    }
+
+    #[test]
+    fn config_invalid_rps() {
+        let b = HashMapBinding::new("test-name", map! {
+            "requests-per-second" => "-1",
+        });
+
+        assert_eq!(Err(InvalidBindingError::new("invalid requests-per-second -1: invalid digit found in string")), ratelimit::config(&b))
+    }
+}
@@ -0,0 +1,187 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::binding::{Binding, InvalidBindingError};
+use crate::integrations::{self, Credentials};
+
+/// The key for the host of a Redis `Binding`.
+pub const HOST: &str = "host";
+
+/// The key for the port of a Redis `Binding`.
+pub const PORT: &str = "port";
+
+/// The key for the database number of a Redis `Binding`.
+pub const DATABASE: &str = "database";
+
+/// The key for the username of a Redis `Binding`.
+pub const USERNAME: &str = "username";
+
+/// The key for the password of a Redis `Binding`.
+pub const PASSWORD: &str = "password";
+
+/// The default port of a Redis server.
+pub const DEFAULT_PORT: u16 = 6379;
+
+/// Creates a `redis://` connection URL from a Redis `Binding`.  The username and password are percent-encoded and the
+/// database, if present, is added as the path.
+///
+/// * `binding` - the `Binding` to read the configuration from
+///
+/// returns the connection URL if the `Binding` contains a `host`, otherwise an `InvalidBindingError`
+pub fn url(binding: &impl Binding) -> Result<String, InvalidBindingError> {
+    if let Some(d) = binding.get(DATABASE) {
+        database(&d)?;
+    }
+
+    return integrations::assemble_url(binding, "redis", Credentials::Include, Some(DEFAULT_PORT));
+}
+
+/// Creates a `redis::ConnectionInfo` from a Redis `Binding`.  The port defaults to `DEFAULT_PORT` and the database
+/// defaults to `0`.
+///
+/// * `binding` - the `Binding` to read the configuration from
+///
+/// returns the `redis::ConnectionInfo` if the `Binding` contains a `host`, otherwise an `InvalidBindingError`
+#[cfg(feature = "redis")]
+pub fn connection_info(binding: &impl Binding) -> Result<redis::ConnectionInfo, InvalidBindingError> {
+    let host = binding.get(HOST)
+        .ok_or_else(|| InvalidBindingError::new("binding does not contain a host"))?;
+
+    let db = match binding.get(DATABASE) {
+        None => 0,
+        Some(d) => database(&d)?,
+    };
+
+    return Ok(redis::ConnectionInfo {
+        addr: redis::ConnectionAddr::Tcp(host, integrations::port(binding)?.unwrap_or(DEFAULT_PORT)),
+        redis: redis::RedisConnectionInfo {
+            db,
+            username: binding.get(USERNAME),
+            password: binding.get(PASSWORD),
+            ..Default::default()
+        },
+    });
+}
+
+fn database(d: &str) -> Result<i64, InvalidBindingError> {
+    return d.parse::<i64>()
+        .map_err(|e| InvalidBindingError::new(format!("invalid database {}: {}", d, e)));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::binding::{HashMapBinding, InvalidBindingError};
+    use crate::integrations::redis;
+
+    #[test]
+    fn url() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "port" => "6380",
+            "database" => "2",
+            "password" => "test p@ssword",
+        });
+
+        assert_eq!(Ok(String::from("redis://:test%20p%40ssword@test-host:6380/2")), redis::url(&b))
+    }
+
+    #[test]
+    fn url_host_only() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+        });
+
+        assert_eq!(Ok(String::from("redis://test-host:6379")), redis::url(&b))
+    }
+
+    #[test]
+    fn url_missing_host() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert_eq!(Err(InvalidBindingError::new("binding does not contain a host")), redis::url(&b))
+    }
+
+    #[test]
+    fn url_empty_host() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "",
+        });
+
+        assert_eq!(Err(InvalidBindingError::new("binding does not contain a host")), redis::url(&b))
+    }
+
+    #[test]
+    fn url_ipv6() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "::1",
+            "username" => "test-username",
+        });
+
+        assert_eq!(Ok(String::from("redis://test-username@[::1]:6379")), redis::url(&b))
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn connection_info_valid() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "port" => "6380",
+            "database" => "2",
+            "username" => "test-username",
+            "password" => "test-password",
+        });
+
+        let c = redis::connection_info(&b).unwrap();
+        assert_eq!(::redis::ConnectionAddr::Tcp(String::from("test-host"), 6380), c.addr);
+        assert_eq!(2, c.redis.db);
+        assert_eq!(Some(String::from("test-username")), c.redis.username);
+        assert_eq!(Some(String::from("test-password")), c.redis.password);
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn connection_info_defaults() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+        });
+
+        let c = redis::connection_info(&b).unwrap();
+        assert_eq!(::redis::ConnectionAddr::Tcp(String::from("test-host"), 6379), c.addr);
+        assert_eq!(0, c.redis.db);
+        assert_eq!(None, c.redis.username);
+        assert_eq!(None, c.redis.password);
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn connection_info_missing_host() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert_eq!(Some(InvalidBindingError::new("binding does not contain a host")), redis::connection_info(&b).err())
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn connection_info_invalid_database() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "database" => "test-database",
+        });
+
+        assert_eq!(Some(InvalidBindingError::new("invalid database test-database: invalid digit found in string")),
+                   redis::connection_info(&b).err())
+    }
+}
@@ -0,0 +1,265 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+
+use crate::binding::{Binding, InvalidBindingError};
+
+/// The key for the host of an SMTP `Binding`.
+pub const HOST: &str = "host";
+
+/// The key for the port of an SMTP `Binding`.
+pub const PORT: &str = "port";
+
+/// The key for the username of an SMTP `Binding`.
+pub const USERNAME: &str = "username";
+
+/// The key for the password of an SMTP `Binding`.
+pub const PASSWORD: &str = "password";
+
+/// The key for the TLS mode of an SMTP `Binding`: `none`, `opportunistic`, `starttls`, or `implicit`.
+pub const TLS: &str = "tls";
+
+/// The TLS mode of an SMTP connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlsMode {
+    /// Connect without TLS.
+    None,
+
+    /// Upgrade the connection with `STARTTLS` if the server supports it.
+    Opportunistic,
+
+    /// Require the connection to be upgraded with `STARTTLS`.
+    StartTls,
+
+    /// Connect with TLS from the start.
+    Implicit,
+}
+
+/// The settings of an SMTP connection.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// The host of the server.
+    pub host: String,
+
+    /// The port of the server.
+    pub port: u16,
+
+    /// The TLS mode of the connection.
+    pub tls: TlsMode,
+
+    /// The username and password to authenticate with, if any.
+    pub credentials: Option<(String, String)>,
+}
+
+/// Prints the settings with the password replaced by `***` so that secrets are not logged.
+impl fmt::Debug for Settings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.debug_struct("Settings")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("credentials", &self.credentials.as_ref().map(|(u, _)| (u, "***")))
+            .finish();
+    }
+}
+
+/// Reads the `Settings` of an SMTP `Binding`.  The TLS mode defaults to `starttls` and the port defaults to the
+/// standard port for the TLS mode (465 for `implicit`, 587 for `starttls`, and 25 otherwise).  Credentials are only
+/// configured if both `username` and `password` exist.
+///
+/// * `binding` - the `Binding` to read the configuration from
+///
+/// returns the `Settings` if the `Binding` contains a `host`, otherwise an `InvalidBindingError`
+pub fn settings(binding: &impl Binding) -> Result<Settings, InvalidBindingError> {
+    let host = binding.get(HOST)
+        .ok_or_else(|| InvalidBindingError::new("binding does not contain a host"))?;
+
+    let (tls, default_port) = match binding.get(TLS).map(|t| t.to_ascii_lowercase()).as_deref() {
+        Some("none") => (TlsMode::None, 25),
+        Some("opportunistic") => (TlsMode::Opportunistic, 25),
+        None | Some("starttls") => (TlsMode::StartTls, 587),
+        Some("implicit") => (TlsMode::Implicit, 465),
+        Some(t) => return Err(InvalidBindingError::new(format!("invalid TLS mode: {}", t))),
+    };
+
+    let port = match binding.get(PORT) {
+        None => default_port,
+        Some(p) => p.parse::<u16>()
+            .map_err(|e| InvalidBindingError::new(format!("invalid port {}: {}", p, e)))?,
+    };
+
+    let credentials = match (binding.get(USERNAME), binding.get(PASSWORD)) {
+        (Some(u), Some(p)) => Some((u, p)),
+        _ => None,
+    };
+
+    return Ok(Settings { host, port, tls, credentials });
+}
+
+/// Creates a `lettre::transport::smtp::SmtpTransportBuilder` from an SMTP `Binding`, configured with its `Settings`.
+///
+/// * `binding` - the `Binding` to read the configuration from
+///
+/// returns the `SmtpTransportBuilder` if the `Binding` contains valid `Settings`, otherwise an `InvalidBindingError`
+#[cfg(feature = "lettre")]
+pub fn lettre_transport(binding: &impl Binding) -> Result<lettre::transport::smtp::SmtpTransportBuilder, InvalidBindingError> {
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::transport::smtp::client::{Tls, TlsParameters};
+    use lettre::SmtpTransport;
+
+    let s = settings(binding)?;
+
+    let parameters = || {
+        return TlsParameters::new(s.host.to_string())
+            .map_err(|e| InvalidBindingError::new(format!("unable to configure TLS for {}: {}", s.host, e)));
+    };
+
+    let tls = match s.tls {
+        TlsMode::None => Tls::None,
+        TlsMode::Opportunistic => Tls::Opportunistic(parameters()?),
+        TlsMode::StartTls => Tls::Required(parameters()?),
+        TlsMode::Implicit => Tls::Wrapper(parameters()?),
+    };
+
+    let mut b = SmtpTransport::builder_dangerous(s.host.to_string())
+        .port(s.port)
+        .tls(tls);
+
+    if let Some((u, p)) = s.credentials {
+        b = b.credentials(Credentials::new(u, p));
+    }
+
+    return Ok(b);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binding::{HashMapBinding, InvalidBindingError};
+    use crate::integrations::smtp;
+    use crate::integrations::smtp::{Settings, TlsMode};
+
+    #[test]
+    fn settings_valid() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "port" => "2525",
+            "username" => "test-username",
+            "password" => "test-password",
+        });
+
+        assert_eq!(Ok(Settings {
+            host: String::from("test-host"),
+            port: 2525,
+            tls: TlsMode::StartTls,
+            credentials: Some((String::from("test-username"), String::from("test-password"))),
+        }), smtp::settings(&b))
+    }
+
+    #[test]
+    fn settings_default_port() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "tls" => "implicit",
+        });
+
+        let s = smtp::settings(&b).unwrap();
+        assert_eq!(465, s.port);
+        assert_eq!(TlsMode::Implicit, s.tls);
+        assert_eq!(None, s.credentials);
+    }
+
+    #[test]
+    fn settings_missing_host() {
+        let b = HashMapBinding::new("test-name", map! {
+            "port" => "2525",
+        });
+
+        assert_eq!(Err(InvalidBindingError::new("binding does not contain a host")), smtp::settings(&b))
+    }
+
+    #[test]
+    fn settings_invalid_tls() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "tls" => "sometimes",
+        });
+
+        assert_eq!(Err(InvalidBindingError::new("invalid TLS mode: sometimes")), smtp::settings(&b))
+    }
+
+    #[test]
+    fn settings_debug() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "username" => "test-username",
+            "password" => "test-password",
+        });
+
+        assert!(!format!("{:?}", smtp::settings(&b).unwrap()).contains("test-password"))
+    }
+
+    #[cfg(feature = "lettre")]
+    #[test]
+    fn lettre_transport() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "port" => "2525",
+            "username" => "test-username",
+            "password" => "test-password",
+        });
+
+        assert_eq!(Ok(Settings {
+            host: String::from("test-host"),
+            port: 2525,
+            tls: TlsMode::StartTls,
+            credentials: Some((String::from("test-username"), String::from("test-password"))),
+        }), smtp::settings(&b));
+        assert!(smtp::lettre_transport(&b).is_ok())
+    }
+
+    #[cfg(feature = "lettre")]
+    #[test]
+    fn lettre_transport_tls_modes() {
+        for (t, m, p) in [
+            ("none", TlsMode::None, 25),
+            ("opportunistic", TlsMode::Opportunistic, 25),
+            ("starttls", TlsMode::StartTls, 587),
+            ("implicit", TlsMode::Implicit, 465),
+        ] {
+            let b = HashMapBinding::new("test-name", map! {
+                "host" => "test-host",
+                "tls" => t,
+            });
+
+            let s = smtp::settings(&b).unwrap();
+            assert_eq!(m, s.tls);
+            assert_eq!(p, s.port);
+            assert_eq!(None, s.credentials);
+            assert!(smtp::lettre_transport(&b).is_ok());
+        }
+    }
+
+    #[cfg(feature = "lettre")]
+    #[test]
+    fn lettre_transport_missing_host() {
+        let b = HashMapBinding::new("test-name", map! {
+            "port" => "2525",
+        });
+
+        assert_eq!(Some(InvalidBindingError::new("binding does not contain a host")), smtp::lettre_transport(&b).err())
+    }
+}
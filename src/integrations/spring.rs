@@ -0,0 +1,133 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::binding::Binding;
+use crate::bindings::normalize_label;
+use crate::integrations::{self, Credentials};
+
+/// Returns the environment variables that
+/// [Spring Cloud Bindings](https://github.com/spring-cloud/spring-cloud-bindings) would contribute for a `Binding`, in
+/// their relaxed-binding form (e.g. `SPRING_DATASOURCE_URL` for `spring.datasource.url`).  Only the `postgresql` and
+/// `mysql` types are mapped.  Other types and `Binding`s without a type return an empty collection.
+///
+/// * `binding` - the `Binding` to map
+///
+/// returns the environment variables for the `Binding`
+pub fn to_spring_env(binding: &impl Binding) -> Vec<(String, String)> {
+    let Ok(t) = binding.get_type() else {
+        return Vec::new();
+    };
+
+    let (driver, scheme) = match normalize_label(&t).as_str() {
+        "postgresql" => ("org.postgresql.Driver", "postgresql"),
+        "mysql" => ("org.mariadb.jdbc.Driver", "mysql"),
+        _ => return Vec::new(),
+    };
+
+    let mut e = vec![(String::from("SPRING_DATASOURCE_DRIVERCLASSNAME"), String::from(driver))];
+
+    if let Some(u) = binding.get("jdbc-url").or_else(|| integrations::assemble_url(binding, &format!("jdbc:{}", scheme), Credentials::Omit, None).ok()) {
+        e.push((String::from("SPRING_DATASOURCE_URL"), u));
+    }
+
+    if let Some(u) = binding.get("r2dbc-url").or_else(|| integrations::assemble_url(binding, &format!("r2dbc:{}", scheme), Credentials::Omit, None).ok()) {
+        e.push((String::from("SPRING_R2DBC_URL"), u));
+    }
+
+    for (k, p) in [("username", "USERNAME"), ("password", "PASSWORD")] {
+        if let Some(v) = binding.get(k) {
+            e.push((format!("SPRING_DATASOURCE_{}", p), v.to_string()));
+            e.push((format!("SPRING_R2DBC_{}", p), v));
+        }
+    }
+
+    return e;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::binding::HashMapBinding;
+    use crate::integrations::spring;
+
+    #[test]
+    fn to_spring_env_postgresql() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "postgresql",
+            "host" => "test-host",
+            "port" => "5432",
+            "database" => "test-database",
+            "username" => "test-username",
+            "password" => "test-password",
+        });
+
+        assert_eq!(vec![
+            (String::from("SPRING_DATASOURCE_DRIVERCLASSNAME"), String::from("org.postgresql.Driver")),
+            (String::from("SPRING_DATASOURCE_URL"), String::from("jdbc:postgresql://test-host:5432/test-database")),
+            (String::from("SPRING_R2DBC_URL"), String::from("r2dbc:postgresql://test-host:5432/test-database")),
+            (String::from("SPRING_DATASOURCE_USERNAME"), String::from("test-username")),
+            (String::from("SPRING_R2DBC_USERNAME"), String::from("test-username")),
+            (String::from("SPRING_DATASOURCE_PASSWORD"), String::from("test-password")),
+            (String::from("SPRING_R2DBC_PASSWORD"), String::from("test-password")),
+        ], spring::to_spring_env(&b));
+    }
+
+    #[test]
+    fn to_spring_env_encoded_url() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "mysql",
+            "host" => "::1",
+            "database" => "test database",
+        });
+
+        assert_eq!(vec![
+            (String::from("SPRING_DATASOURCE_DRIVERCLASSNAME"), String::from("org.mariadb.jdbc.Driver")),
+            (String::from("SPRING_DATASOURCE_URL"), String::from("jdbc:mysql://[::1]/test%20database")),
+            (String::from("SPRING_R2DBC_URL"), String::from("r2dbc:mysql://[::1]/test%20database")),
+        ], spring::to_spring_env(&b));
+    }
+
+    #[test]
+    fn to_spring_env_explicit_urls() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "mysql",
+            "jdbc-url" => "jdbc:mysql://test-jdbc-host/test-database",
+            "r2dbc-url" => "r2dbc:mysql://test-r2dbc-host/test-database",
+        });
+
+        assert_eq!(vec![
+            (String::from("SPRING_DATASOURCE_DRIVERCLASSNAME"), String::from("org.mariadb.jdbc.Driver")),
+            (String::from("SPRING_DATASOURCE_URL"), String::from("jdbc:mysql://test-jdbc-host/test-database")),
+            (String::from("SPRING_R2DBC_URL"), String::from("r2dbc:mysql://test-r2dbc-host/test-database")),
+        ], spring::to_spring_env(&b));
+    }
+
+    #[test]
+    fn to_spring_env_unknown_type() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "test-type-1",
+        });
+
+        assert!(spring::to_spring_env(&b).is_empty())
+    }
+
+    #[test]
+    fn to_spring_env_missing_type() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert!(spring::to_spring_env(&b).is_empty())
+    }
+}
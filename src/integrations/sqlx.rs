@@ -0,0 +1,97 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(feature = "sqlx")]
+use crate::binding::{Binding, InvalidBindingError};
+#[cfg(feature = "sqlx")]
+use crate::integrations::{mysql, postgres};
+
+/// Creates a `sqlx::any::AnyConnectOptions` from a SQL `Binding`, dispatching on its type to the matching driver.  The
+/// `postgresql` and `mysql` types are supported.  Callers must install the drivers with
+/// `sqlx::any::install_default_drivers` before connecting.
+///
+/// * `binding` - the `Binding` to read the configuration from
+///
+/// returns the `AnyConnectOptions` if the `Binding` is of a supported SQL type and is valid, otherwise an
+/// `InvalidBindingError`
+#[cfg(feature = "sqlx")]
+pub fn any_connect_options(binding: &impl Binding) -> Result<sqlx::any::AnyConnectOptions, InvalidBindingError> {
+    let u = match binding.get_type_normalized()?.as_str() {
+        "postgresql" => postgres::url(binding)?,
+        "mysql" => mysql::url(binding)?,
+        t => return Err(InvalidBindingError::new(format!("unsupported SQL binding type {}", t))),
+    };
+
+    return u.parse()
+        .map_err(|e| InvalidBindingError::new(format!("invalid connection URL: {}", e)));
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlx")]
+    use crate::binding::{HashMapBinding, InvalidBindingError};
+    #[cfg(feature = "sqlx")]
+    use crate::integrations::sqlx;
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn any_connect_options_postgresql() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "postgresql",
+            "host" => "test-host",
+            "port" => "5432",
+            "database" => "test-database",
+            "username" => "test-username",
+            "password" => "test-password",
+        });
+
+        let u = sqlx::any_connect_options(&b).unwrap().database_url;
+        assert_eq!("postgresql", u.scheme());
+        assert_eq!(Some("test-host"), u.host_str());
+        assert_eq!(Some(5432), u.port());
+        assert_eq!("/test-database", u.path());
+        assert_eq!("test-username", u.username());
+        assert_eq!(Some("test-password"), u.password());
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn any_connect_options_mysql() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "mysql",
+            "host" => "test-host",
+            "port" => "3306",
+            "database" => "test-database",
+        });
+
+        let u = sqlx::any_connect_options(&b).unwrap().database_url;
+        assert_eq!("mysql", u.scheme());
+        assert_eq!(Some("test-host"), u.host_str());
+        assert_eq!(Some(3306), u.port());
+        assert_eq!("/test-database", u.path());
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn any_connect_options_unsupported_type() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "redis",
+            "host" => "test-host",
+        });
+
+        assert_eq!(Some(InvalidBindingError::new("unsupported SQL binding type redis")), sqlx::any_connect_options(&b).err())
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use crate::binding::{Binding, InvalidBindingError};
+
+/// The keys, in order of precedence, that may contain the SSL mode of a `Binding`.
+pub const KEYS: [&str; 3] = ["sslmode", "ssl", "tls"];
+
+/// The SSL mode to use when connecting to a SQL database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SslMode {
+    /// Only try a non-SSL connection.
+    Disable,
+
+    /// First try a non-SSL connection and, if that fails, try an SSL connection.
+    Allow,
+
+    /// First try an SSL connection and, if that fails, try a non-SSL connection.
+    Prefer,
+
+    /// Only try an SSL connection.
+    Require,
+
+    /// Only try an SSL connection and verify that the server certificate is issued by a trusted CA.
+    VerifyCa,
+
+    /// Only try an SSL connection, verify that the server certificate is issued by a trusted CA, and that the
+    /// requested server host name matches that in the certificate.
+    VerifyFull,
+}
+
+impl SslMode {
+    /// Returns the SSL mode of a `Binding`, reading the first of `KEYS` that exists.
+    ///
+    /// * `binding` - the `Binding` to read the SSL mode from
+    ///
+    /// returns the SSL mode of the `Binding` if it exists, otherwise `None`
+    pub fn from_binding(binding: &impl Binding) -> Option<Result<SslMode, InvalidBindingError>> {
+        return KEYS.iter()
+            .find_map(|k| binding.get(k))
+            .map(|v| v.parse());
+    }
+
+    /// Returns the SSL mode formatted as a PostgreSQL `sslmode` parameter value.
+    ///
+    /// returns the SSL mode formatted as a PostgreSQL `sslmode` parameter value
+    pub fn as_postgres(&self) -> &'static str {
+        return match self {
+            SslMode::Disable => "disable",
+            SslMode::Allow => "allow",
+            SslMode::Prefer => "prefer",
+            SslMode::Require => "require",
+            SslMode::VerifyCa => "verify-ca",
+            SslMode::VerifyFull => "verify-full",
+        };
+    }
+
+    /// Returns the SSL mode formatted as a MySQL `ssl-mode` parameter value.  MySQL has no equivalent of `Allow`, so
+    /// it is formatted as `PREFERRED`.
+    ///
+    /// returns the SSL mode formatted as a MySQL `ssl-mode` parameter value
+    pub fn as_mysql(&self) -> &'static str {
+        return match self {
+            SslMode::Disable => "DISABLED",
+            SslMode::Allow | SslMode::Prefer => "PREFERRED",
+            SslMode::Require => "REQUIRED",
+            SslMode::VerifyCa => "VERIFY_CA",
+            SslMode::VerifyFull => "VERIFY_IDENTITY",
+        };
+    }
+}
+
+impl FromStr for SslMode {
+    type Err = InvalidBindingError;
+
+    /// Parses an SSL mode from the common spellings used by PostgreSQL and MySQL, as well as boolean values.
+    /// Comparison is case-insensitive and `_` is equivalent to `-`.
+    fn from_str(s: &str) -> Result<SslMode, InvalidBindingError> {
+        return match s.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "disable" | "disabled" | "false" | "off" | "no" | "0" => Ok(SslMode::Disable),
+            "allow" | "allowed" => Ok(SslMode::Allow),
+            "prefer" | "preferred" => Ok(SslMode::Prefer),
+            "require" | "required" | "true" | "on" | "yes" | "1" => Ok(SslMode::Require),
+            "verify-ca" => Ok(SslMode::VerifyCa),
+            "verify-full" | "verify-identity" => Ok(SslMode::VerifyFull),
+            _ => Err(InvalidBindingError::new(format!("invalid SSL mode: {}", s))),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::binding::{HashMapBinding, InvalidBindingError};
+    use crate::integrations::ssl::SslMode;
+
+    #[test]
+    fn parse_require() {
+        assert_eq!(Ok(SslMode::Require), "require".parse());
+        assert_eq!(Ok(SslMode::Require), "REQUIRED".parse());
+    }
+
+    #[test]
+    fn parse_verify_full() {
+        assert_eq!(Ok(SslMode::VerifyFull), "verify-full".parse());
+        assert_eq!(Ok(SslMode::VerifyFull), "VERIFY_IDENTITY".parse());
+    }
+
+    #[test]
+    fn parse_boolean() {
+        assert_eq!(Ok(SslMode::Require), "true".parse());
+        assert_eq!(Ok(SslMode::Disable), "false".parse());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(Err(InvalidBindingError::new("invalid SSL mode: sometimes")), "sometimes".parse::<SslMode>());
+    }
+
+    #[test]
+    fn from_binding_missing() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert_eq!(None, SslMode::from_binding(&b))
+    }
+
+    #[test]
+    fn from_binding_precedence() {
+        let b = HashMapBinding::new("test-name", map! {
+            "sslmode" => "verify-ca",
+            "tls" => "false",
+        });
+
+        assert_eq!(Some(Ok(SslMode::VerifyCa)), SslMode::from_binding(&b))
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!("verify-full", SslMode::VerifyFull.as_postgres());
+        assert_eq!("VERIFY_IDENTITY", SslMode::VerifyFull.as_mysql());
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// Declares a module of `Binding` key names.  Each key becomes a `const &str`, and the module also contains an `ALL`
+/// array of the declared keys, a `Key` type whose only values are the declared keys, and a `get` accessor that accepts
+/// a `Key`, so that reading an undeclared key becomes a compile error rather than a silently missing value.
+///
+/// ```
+/// use service_bindings::binding::HashMapBinding;
+/// use service_bindings::declare_keys;
+///
+/// declare_keys! {
+///     pub mod keys {
+///         HOST => "host",
+///         PORT => "port",
+///     }
+/// }
+///
+/// let b = HashMapBinding::new("test-name", Default::default());
+/// assert_eq!(None, keys::get(&b, keys::Key::HOST));
+/// ```
+///
+/// A `Key` cannot be created for an undeclared key:
+///
+/// ```compile_fail
+/// use service_bindings::binding::HashMapBinding;
+/// use service_bindings::declare_keys;
+///
+/// declare_keys! {
+///     pub mod keys {
+///         HOST => "host",
+///     }
+/// }
+///
+/// let b = HashMapBinding::new("test-name", Default::default());
+/// keys::get(&b, keys::Key::HOTS);
+/// ```
+#[macro_export]
+macro_rules! declare_keys {
+    ($vis:vis mod $name:ident { $($(#[$m:meta])* $key:ident => $value:literal),* $(,)? }) => {
+        $vis mod $name {
+            $(
+                $(#[$m])*
+                pub const $key: &str = $value;
+            )*
+
+            /// All of the keys declared in this module.
+            pub const ALL: &[&str] = &[$($key),*];
+
+            /// A key declared in this module.  Values can only be created for the declared keys.
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub struct Key(&'static str);
+
+            #[allow(dead_code)]
+            impl Key {
+                $(
+                    $(#[$m])*
+                    pub const $key: Key = Key($key);
+                )*
+
+                /// Returns the name of the key.
+                ///
+                /// returns the name of the key
+                pub fn as_str(self) -> &'static str {
+                    return self.0;
+                }
+            }
+
+            /// Returns the contents of a declared `Binding` entry in its trimmed UTF-8 form.
+            ///
+            /// * `binding` - the `Binding` to read the entry from
+            /// * `key` - the declared key of the entry to retrieve
+            ///
+            /// returns the contents of the entry if it exists, otherwise `None`
+            #[allow(dead_code)]
+            pub fn get(binding: &impl $crate::binding::Binding, key: Key) -> Option<String> {
+                return $crate::binding::Binding::get(binding, key.0);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binding::HashMapBinding;
+
+    declare_keys! {
+        mod keys {
+            /// The host.
+            HOST => "host",
+            PORT => "port",
+        }
+    }
+
+    #[test]
+    fn declare_keys() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => " test-host ",
+        });
+
+        assert_eq!("host", keys::HOST);
+        assert_eq!(&["host", "port"], keys::ALL);
+        assert_eq!("host", keys::Key::HOST.as_str());
+        assert_eq!(Some(String::from("test-host")), keys::get(&b, keys::Key::HOST));
+        assert_eq!(None, keys::get(&b, keys::Key::PORT));
+    }
+}
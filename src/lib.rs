@@ -14,10 +14,18 @@
  * limitations under the License.
  */
 
+#![allow(clippy::needless_return)]
+
 #[cfg(test)]
 #[macro_use]
 mod map;
 
 pub mod binding;
 pub mod bindings;
+#[cfg(feature = "serde")]
+mod de;
+pub mod export;
+pub mod integrations;
+mod keys;
 mod secret;
+pub mod validation;
@@ -17,6 +17,9 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 
+/// The maximum length of a Kubernetes Secret key.
+const MAX_SECRET_KEY_LENGTH: usize = 253;
+
 lazy_static! {
     static ref VALID_SECRET_KEY: Regex = Regex::new(r"^[A-Za-z0-9\-_.]+$").unwrap();
 }
@@ -28,7 +31,7 @@ lazy_static! {
 ///
 /// returns `true` if the `str` is a valid Kubernetes Secret key, otherwise `false`
 pub fn is_valid_secret_key(key: &str) -> bool {
-    return VALID_SECRET_KEY.is_match(key);
+    return key.len() <= MAX_SECRET_KEY_LENGTH && key != "." && key != ".." && VALID_SECRET_KEY.is_match(key);
 }
 
 #[cfg(test)]
@@ -63,4 +66,22 @@ mod tests {
             assert!(!is_valid_secret_key(v));
         }
     }
+
+    #[test]
+    fn is_valid_secret_key_dot_segments() {
+        assert!(!is_valid_secret_key("."));
+        assert!(!is_valid_secret_key(".."));
+        assert!(is_valid_secret_key("..."));
+    }
+
+    #[test]
+    fn is_valid_secret_key_empty() {
+        assert!(!is_valid_secret_key(""));
+    }
+
+    #[test]
+    fn is_valid_secret_key_length() {
+        assert!(is_valid_secret_key(&"a".repeat(253)));
+        assert!(!is_valid_secret_key(&"a".repeat(254)));
+    }
 }
@@ -0,0 +1,605 @@
+/*
+ * Copyright 2021 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str;
+
+use crate::binding::{Binding, InvalidBindingError, PROVIDER, TYPE};
+use crate::bindings::normalize_label;
+use crate::secret;
+
+/// A problem detected while auditing a `Binding`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// The `Binding` does not contain a type.
+    MissingType,
+
+    /// The `Binding` contains a type, but it is empty.
+    EmptyType,
+
+    /// The value of the entry with the given key is not valid UTF-8.
+    NonUtf8Value(String),
+
+    /// The given key is not a valid Kubernetes Secret key, so its entry cannot be read.
+    InvalidKey(String),
+}
+
+/// The result of auditing a `Binding`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindingAudit {
+    /// The name of the `Binding`.
+    pub name: String,
+
+    /// The type of the `Binding`, or `None` if it is missing or empty.
+    pub binding_type: Option<String>,
+
+    /// The provider of the `Binding`, or `None` if it is missing or empty.
+    pub provider: Option<String>,
+
+    /// The number of entries in the `Binding`.
+    pub key_count: usize,
+
+    /// The problems detected in the `Binding`.
+    pub problems: Vec<Problem>,
+}
+
+/// Audits a collection of `Binding`s, reporting the metadata of each and any problems detected.  Keys that are not
+/// valid Kubernetes Secret keys are found in the unfiltered listing returned by `get_raw_keys`.
+///
+/// * `bindings` - the `Binding`s to audit
+///
+/// returns an audit of each `Binding`
+pub fn audit(bindings: &[impl Binding]) -> Vec<BindingAudit> {
+    return bindings.iter()
+        .map(|b| {
+            let mut keys = b.get_keys();
+            keys.sort();
+
+            let mut problems = Vec::new();
+
+            match b.get_as_bytes(TYPE) {
+                None => problems.push(Problem::MissingType),
+                Some(t) if t.trim_ascii().is_empty() => problems.push(Problem::EmptyType),
+                _ => {}
+            }
+
+            if let Err(k) = validate_utf8(b) {
+                problems.extend(k.into_iter().map(Problem::NonUtf8Value));
+            }
+
+            let mut raw = b.get_raw_keys();
+            raw.sort();
+            problems.extend(raw.into_iter()
+                .filter(|k| !secret::is_valid_secret_key(k))
+                .map(Problem::InvalidKey));
+
+            let text = |k: &str| {
+                return b.get_as_bytes(k)
+                    .and_then(|v| str::from_utf8(&v).ok().map(|s| s.trim().to_string()))
+                    .filter(|s| !s.is_empty());
+            };
+
+            return BindingAudit {
+                name: b.get_name(),
+                binding_type: text(TYPE),
+                provider: text(PROVIDER),
+                key_count: keys.len(),
+                problems,
+            };
+        })
+        .collect();
+}
+
+/// Returns the fraction of required keys that are present in a `Binding`, so that partially-provisioned bindings can be
+/// surfaced.  A `Binding` with no required keys has full coverage.
+///
+/// * `binding` - the `Binding` to inspect
+/// * `required` - the keys that are required
+///
+/// returns the fraction of required keys that are present, from `0.0` to `1.0`
+pub fn coverage(binding: &impl Binding, required: &[&str]) -> f64 {
+    if required.is_empty() {
+        return 1.0;
+    }
+
+    let present = required.iter()
+        .filter(|k| binding.get_as_bytes(k).is_some())
+        .count();
+
+    return present as f64 / required.len() as f64;
+}
+
+/// Returns the keys that are present in both `Binding`s with different values, so that the precedence of overlaid or
+/// merged sources can be debugged.  Values are compared byte for byte.
+///
+/// * `a` - the first `Binding` to compare
+/// * `b` - the second `Binding` to compare
+///
+/// returns the conflicting keys, in sorted order
+pub fn conflicts(a: &impl Binding, b: &impl Binding) -> Vec<String> {
+    return a.get_keys_sorted().into_iter()
+        .filter(|k| match (a.get_as_bytes(k), b.get_as_bytes(k)) {
+            (Some(v), Some(w)) => v != w,
+            _ => false,
+        })
+        .collect();
+}
+
+/// Validates a `Binding` against a [JSON Schema](https://json-schema.org).  The entries of the `Binding` whose values
+/// are valid UTF-8 are materialized, trimmed, as the string properties of a JSON object which is then validated.
+///
+/// * `binding` - the `Binding` to validate
+/// * `schema` - the JSON Schema to validate against
+///
+/// returns `Ok` if the `Binding` is valid, otherwise all of the violations, or the error if `schema` is invalid
+#[cfg(feature = "jsonschema")]
+pub fn validate_schema(binding: &impl Binding, schema: &serde_json::Value) -> Result<(), Vec<String>> {
+    let v = jsonschema::validator_for(schema)
+        .map_err(|e| vec![format!("invalid schema: {}", e)])?;
+
+    let instance: serde_json::Map<String, serde_json::Value> = binding.to_sorted_map().into_iter()
+        .filter_map(|(k, v)| String::from_utf8(v).ok().map(|v| (k, serde_json::Value::String(v.trim().to_string()))))
+        .collect();
+    let instance = serde_json::Value::Object(instance);
+
+    let violations: Vec<String> = v.iter_errors(&instance)
+        .map(|e| e.to_string())
+        .collect();
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    return Err(violations);
+}
+
+/// Validates that the values of all entries in a `Binding` are valid UTF-8.  Binary values such as keystores are
+/// legitimate, but are a red flag for keys that are expected to be text.
+///
+/// * `binding` - the `Binding` to validate
+///
+/// returns `Ok` if all values are valid UTF-8, otherwise the sorted keys of the values that are not
+pub fn validate_utf8(binding: &impl Binding) -> Result<(), Vec<String>> {
+    let mut invalid: Vec<String> = binding.get_keys().into_iter()
+        .filter(|k| binding.get_as_bytes(k).is_some_and(|v| str::from_utf8(&v).is_err()))
+        .collect();
+
+    if invalid.is_empty() {
+        return Ok(());
+    }
+
+    invalid.sort();
+    return Err(invalid);
+}
+
+/// Returns a single-line, Kubernetes-style event describing a `Binding` that failed validation, such as
+/// `reason=InvalidBinding binding=test-name message="binding does not contain a host"`, so that failures surface
+/// consistently in logs.  Only the name of the `Binding` and the message are included, never its values.
+///
+/// * `binding` - the `Binding` that failed validation
+/// * `err` - the error describing the failure
+///
+/// returns the event
+pub fn validation_event(binding: &impl Binding, err: &InvalidBindingError) -> String {
+    return format!("reason=InvalidBinding binding={} message={}", event_value(&binding.get_name()), event_value(&err.to_string()));
+}
+
+fn event_value(s: &str) -> String {
+    if !s.is_empty() && !s.contains(|c: char| c.is_whitespace() || c == '"' || c == '=' || c == '\\') {
+        return s.to_string();
+    }
+
+    return format!("\"{}\"", s.escape_default());
+}
+
+/// Checks that the provider of a `Binding` is consistent with its name, for platforms that encode the provider as a
+/// prefix of the name (e.g. `bitnami-postgresql`).  The name must either equal the provider or start with the provider
+/// followed by a `-`.  Comparison is case-insensitive and a `Binding` without a provider is considered consistent.
+/// This check is opt-in as the convention is platform-specific.
+///
+/// * `binding` - the `Binding` to validate
+///
+/// returns `Ok` if the provider is consistent with the name, otherwise an `InvalidBindingError`
+pub fn check_consistency(binding: &impl Binding) -> Result<(), InvalidBindingError> {
+    let Some(p) = binding.get_provider() else {
+        return Ok(());
+    };
+
+    let n = normalize_label(&binding.get_name());
+    let p = normalize_label(&p);
+
+    if n == p || n.starts_with(&format!("{}-", p)) {
+        return Ok(());
+    }
+
+    return Err(InvalidBindingError::new(format!("binding {} is inconsistent with its provider {}", binding.get_name(), p)));
+}
+
+/// Requires that a `Binding` contains no keys beyond an allowed set, catching typos and stale or misprojected entries.
+/// The `TYPE` and `PROVIDER` keys are always allowed.  Keys are read from `get_raw_keys`, so that entries that are not
+/// valid Kubernetes Secret keys are also reported.
+///
+/// * `binding` - the `Binding` to validate
+/// * `allowed` - the keys that are allowed
+///
+/// returns `Ok` if the `Binding` contains only allowed keys, otherwise an `InvalidBindingError` listing the unexpected
+/// keys
+pub fn require_only_keys(binding: &impl Binding, allowed: &[&str]) -> Result<(), InvalidBindingError> {
+    let mut unexpected: Vec<String> = binding.get_raw_keys().into_iter()
+        .filter(|k| k != TYPE && k != PROVIDER && !allowed.contains(&k.as_str()))
+        .collect();
+
+    if unexpected.is_empty() {
+        return Ok(());
+    }
+
+    unexpected.sort();
+    return Err(InvalidBindingError::new(format!("binding {} contains unexpected keys [{}]",
+                                                binding.get_name(), unexpected.join(", "))));
+}
+
+/// Requires that the provider of a `Binding` is one of an allowed set.  Comparison is case-insensitive.
+///
+/// * `binding` - the `Binding` to validate
+/// * `allowed` - the providers that are allowed
+///
+/// returns `Ok` if the provider is allowed, otherwise an `InvalidBindingError`
+pub fn require_provider_in(binding: &impl Binding, allowed: &[&str]) -> Result<(), InvalidBindingError> {
+    return match binding.get_provider() {
+        None => Err(InvalidBindingError::new(format!("binding {} does not contain a provider", binding.get_name()))),
+        Some(p) => {
+            if allowed.iter().any(|a| normalize_label(a) == normalize_label(&p)) {
+                return Ok(());
+            }
+
+            return Err(InvalidBindingError::new(format!("binding {} has provider {} which is not one of [{}]",
+                                                        binding.get_name(), p, allowed.join(", "))));
+        }
+    };
+}
+
+/// Requires that the type of a `Binding` is one of an allowed set, so that integrations can reject unexpected types
+/// early.  Comparison is case-insensitive.
+///
+/// * `binding` - the `Binding` to validate
+/// * `allowed` - the types that are allowed
+///
+/// returns the normalized type if it is allowed, otherwise an `InvalidBindingError`
+pub fn require_type_in(binding: &impl Binding, allowed: &[&str]) -> Result<String, InvalidBindingError> {
+    let t = binding.get_type_normalized()?;
+
+    if !allowed.iter().any(|a| normalize_label(a) == t) {
+        return Err(InvalidBindingError::new(format!("binding type {} is not one of [{}]", t, allowed.join(", "))));
+    }
+
+    return Ok(t);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::binding::{HashMapBinding, InvalidBindingError};
+    use crate::validation;
+    use crate::validation::{BindingAudit, Problem};
+
+    #[test]
+    fn audit() {
+        let mut binary: HashMap<String, Vec<u8>> = map! {
+            "type" => "test-type-1",
+        };
+        binary.insert(String::from("keystore"), vec![0xfe, 0xed, 0xfe, 0xed]);
+
+        let b = vec![
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "test-type-1",
+                "provider" => "test-provider-1",
+                "host" => "test-host",
+            }),
+            HashMapBinding::new("test-name-2", HashMap::new()),
+            HashMapBinding::new("test-name-3", map! {
+                "type" => " ",
+            }),
+            HashMapBinding::new("test-name-4", binary),
+        ];
+
+        assert_eq!(vec![
+            BindingAudit {
+                name: String::from("test-name-1"),
+                binding_type: Some(String::from("test-type-1")),
+                provider: Some(String::from("test-provider-1")),
+                key_count: 3,
+                problems: vec![],
+            },
+            BindingAudit {
+                name: String::from("test-name-2"),
+                binding_type: None,
+                provider: None,
+                key_count: 0,
+                problems: vec![Problem::MissingType],
+            },
+            BindingAudit {
+                name: String::from("test-name-3"),
+                binding_type: None,
+                provider: None,
+                key_count: 1,
+                problems: vec![Problem::EmptyType],
+            },
+            BindingAudit {
+                name: String::from("test-name-4"),
+                binding_type: Some(String::from("test-type-1")),
+                provider: None,
+                key_count: 2,
+                problems: vec![Problem::NonUtf8Value(String::from("keystore"))],
+            },
+        ], validation::audit(&b));
+    }
+
+    #[test]
+    fn audit_invalid_key() {
+        let b = vec![
+            HashMapBinding::new("test-name-1", map! {
+                "type" => "test-type-1",
+                "lima^mike" => "test-value",
+            }),
+        ];
+
+        assert_eq!(vec![
+            BindingAudit {
+                name: String::from("test-name-1"),
+                binding_type: Some(String::from("test-type-1")),
+                provider: None,
+                key_count: 1,
+                problems: vec![Problem::InvalidKey(String::from("lima^mike"))],
+            },
+        ], validation::audit(&b));
+    }
+
+    #[test]
+    fn check_consistency_consistent() {
+        let b = HashMapBinding::new("Bitnami-PostgreSQL", map! {
+            "provider" => "bitnami",
+        });
+
+        assert_eq!(Ok(()), validation::check_consistency(&b))
+    }
+
+    #[test]
+    fn check_consistency_inconsistent() {
+        let b = HashMapBinding::new("bitnamipostgresql", map! {
+            "provider" => "bitnami",
+        });
+
+        assert_eq!(
+            Err(InvalidBindingError::new("binding bitnamipostgresql is inconsistent with its provider bitnami")),
+            validation::check_consistency(&b))
+    }
+
+    #[test]
+    fn check_consistency_no_provider() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert_eq!(Ok(()), validation::check_consistency(&b))
+    }
+
+    #[test]
+    fn require_only_keys_exact() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "test-type-1",
+            "provider" => "test-provider-1",
+            "host" => "test-host",
+            "port" => "test-port",
+        });
+
+        assert_eq!(Ok(()), validation::require_only_keys(&b, &["host", "port", "password"]))
+    }
+
+    #[test]
+    fn require_only_keys_extra() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "test-type-1",
+            "host" => "test-host",
+            "prot" => "test-port",
+            "hots" => "test-host",
+        });
+
+        assert_eq!(
+            Err(InvalidBindingError::new("binding test-name contains unexpected keys [hots, prot]")),
+            validation::require_only_keys(&b, &["host", "port"]))
+    }
+
+    #[test]
+    fn require_only_keys_invalid_key() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "test-type-1",
+            "host" => "test-host",
+            "lima^mike" => "test-value",
+        });
+
+        assert_eq!(
+            Err(InvalidBindingError::new("binding test-name contains unexpected keys [lima^mike]")),
+            validation::require_only_keys(&b, &["host"]))
+    }
+
+    #[test]
+    fn require_provider_in_allowed() {
+        let b = HashMapBinding::new("test-name", map! {
+            "provider" => "Test-Provider-1",
+        });
+
+        assert_eq!(Ok(()), validation::require_provider_in(&b, &["test-provider-1", "test-provider-2"]))
+    }
+
+    #[test]
+    fn require_provider_in_disallowed() {
+        let b = HashMapBinding::new("test-name", map! {
+            "provider" => "test-provider-3",
+        });
+
+        assert_eq!(
+            Err(InvalidBindingError::new("binding test-name has provider test-provider-3 which is not one of [test-provider-1, test-provider-2]")),
+            validation::require_provider_in(&b, &["test-provider-1", "test-provider-2"]))
+    }
+
+    #[test]
+    fn require_provider_in_absent() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+
+        assert_eq!(
+            Err(InvalidBindingError::new("binding test-name does not contain a provider")),
+            validation::require_provider_in(&b, &["test-provider-1"]))
+    }
+
+    #[test]
+    fn require_type_in() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => " PostgreSQL ",
+        });
+
+        assert_eq!(Ok(String::from("postgresql")), validation::require_type_in(&b, &["mysql", "postgresql"]))
+    }
+
+    #[test]
+    fn require_type_in_disallowed() {
+        let b = HashMapBinding::new("test-name", map! {
+            "type" => "redis",
+        });
+
+        assert_eq!(
+            Err(InvalidBindingError::new("binding type redis is not one of [mysql, postgresql]")),
+            validation::require_type_in(&b, &["mysql", "postgresql"]))
+    }
+
+    #[test]
+    fn require_type_in_missing() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert_eq!(Err(InvalidBindingError::new("binding does not contain a type")), validation::require_type_in(&b, &["postgresql"]))
+    }
+
+    #[test]
+    fn validate_utf8() {
+        let mut c: HashMap<String, Vec<u8>> = map! {
+            "username" => "test-username",
+        };
+        c.insert(String::from("keystore"), vec![0xfe, 0xed, 0xfe, 0xed]);
+
+        assert_eq!(Err(vec![String::from("keystore")]), validation::validate_utf8(&HashMapBinding::new("test-name", c)))
+    }
+
+    #[test]
+    fn validate_utf8_valid() {
+        let b = HashMapBinding::new("test-name", map! {
+            "username" => "test-username",
+        });
+
+        assert_eq!(Ok(()), validation::validate_utf8(&b))
+    }
+
+    #[test]
+    fn coverage_full() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "port" => "5432",
+        });
+
+        assert_eq!(1.0, validation::coverage(&b, &["host", "port"]));
+        assert_eq!(1.0, validation::coverage(&b, &[]))
+    }
+
+    #[test]
+    fn coverage_partial() {
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+        });
+
+        assert_eq!(0.25, validation::coverage(&b, &["host", "port", "username", "password"]))
+    }
+
+    #[test]
+    fn coverage_zero() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert_eq!(0.0, validation::coverage(&b, &["host", "port"]))
+    }
+
+    #[test]
+    fn validation_event() {
+        let b = HashMapBinding::new("test-name", map! {
+            "password" => "test-password",
+        });
+
+        let e = validation::validation_event(&b, &InvalidBindingError::new("binding does not contain a host"));
+        assert_eq!(r#"reason=InvalidBinding binding=test-name message="binding does not contain a host""#, e);
+        assert!(!e.contains("test-password"))
+    }
+
+    #[test]
+    fn validation_event_escaped() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+
+        let e = validation::validation_event(&b, &InvalidBindingError::new("invalid \"value\"\nsecond line"));
+        assert_eq!(r#"reason=InvalidBinding binding=test-name message="invalid \"value\"\nsecond line""#, e);
+        assert_eq!(1, e.lines().count())
+    }
+
+    #[test]
+    fn conflicts() {
+        let a = HashMapBinding::new("test-name-1", map! {
+            "host" => "test-host-1",
+            "port" => "5432",
+            "username" => "test-username",
+        });
+
+        let b = HashMapBinding::new("test-name-2", map! {
+            "host" => "test-host-2",
+            "port" => "5432",
+            "password" => "test-password",
+        });
+
+        assert_eq!(vec!["host"], validation::conflicts(&a, &b));
+        assert!(validation::conflicts(&a, &a).is_empty())
+    }
+
+    #[cfg(feature = "jsonschema")]
+    #[test]
+    fn validate_schema() {
+        let s = serde_json::json!({
+            "type": "object",
+            "required": ["host", "port"],
+            "properties": {
+                "host": { "type": "string" },
+                "port": { "type": "string", "pattern": "^[0-9]+$" },
+            },
+        });
+
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+        });
+
+        assert_eq!(Err(vec![String::from("\"port\" is a required property")]), validation::validate_schema(&b, &s));
+
+        let b = HashMapBinding::new("test-name", map! {
+            "host" => "test-host",
+            "port" => "5432\n",
+        });
+
+        assert_eq!(Ok(()), validation::validate_schema(&b, &s))
+    }
+
+    #[cfg(feature = "jsonschema")]
+    #[test]
+    fn validate_schema_invalid_schema() {
+        let b = HashMapBinding::new("test-name", HashMap::new());
+        assert!(validation::validate_schema(&b, &serde_json::json!({ "type": 1 })).is_err())
+    }
+}